@@ -1,5 +1,7 @@
 use super::models::*;
 use super::parser::IniParser;
+use super::validator::ConfigValidator;
+use super::watcher::FileWatcher;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -11,6 +13,39 @@ impl ConfigLoader {
         Self::build_config(sections)
     }
 
+    /// Load and validate `path`, handing the result to `callback`, then
+    /// keep watching it for changes. On every modification the file is
+    /// re-parsed and re-validated; `callback` is only invoked again if
+    /// validation succeeds, so an operator's bad edit is logged and the
+    /// previous good config keeps running instead of the process
+    /// dropping into an invalid state.
+    pub fn watch<P, F>(path: P, callback: F) -> Result<FileWatcher, String>
+        where P: AsRef<Path>, F: Fn(ServerConfig) + Send + 'static
+    {
+        let path = path.as_ref().to_path_buf();
+
+        let initial = Self::load(&path)?;
+        ConfigValidator::validate(&initial)?;
+        callback(initial);
+
+        let reload_path = path.clone();
+        Ok(
+            FileWatcher::watch(path, move || {
+                match Self::load(&reload_path).and_then(|config| {
+                    ConfigValidator::validate(&config)?;
+                    Ok(config)
+                }) {
+                    Ok(config) => callback(config),
+                    Err(err) =>
+                        eprintln!(
+                            "servex: config reload of {:?} failed, keeping previous config: {}",
+                            reload_path, err
+                        ),
+                }
+            })
+        )
+    }
+
     fn build_config(sections: HashMap<String, HashMap<String, String>>) -> Result<ServerConfig, String> {
         let mut config = ServerConfig::default();
 
@@ -65,6 +100,9 @@ impl ConfigLoader {
             keep_alive: data.get("keep_alive")
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(true),
+            allow_large: data.get("allow_large")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(false),
         })
     }
 
@@ -97,6 +135,8 @@ impl ConfigLoader {
             .map(PathBuf::from)
             .unwrap_or_else(|| PathBuf::from("./www"));
 
+        let tls = Self::parse_tls(data);
+
         Ok(VirtualServer {
             name: name.to_string(),
             host,
@@ -104,6 +144,28 @@ impl ConfigLoader {
             is_default,
             root,
             routes: vec![],
+            tls,
+        })
+    }
+
+    fn parse_tls(data: &HashMap<String, String>) -> Option<TlsConfig> {
+        let cert = data.get("tls_cert").map(PathBuf::from);
+        let key = data.get("tls_key").map(PathBuf::from);
+        let ports: Vec<u16> = data.get("tls_ports")
+            .map(|s| s.split(',').filter_map(|p| p.trim().parse().ok()).collect())
+            .unwrap_or_default();
+
+        // Absent entirely unless at least one tls_* key was set; a
+        // partially-specified section (e.g. ports with no cert) is kept
+        // so the validator can report exactly what's missing.
+        if cert.is_none() && key.is_none() && ports.is_empty() {
+            return None;
+        }
+
+        Some(TlsConfig {
+            cert: cert.unwrap_or_default(),
+            key: key.unwrap_or_default(),
+            ports,
         })
     }
 
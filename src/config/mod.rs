@@ -4,6 +4,7 @@ pub mod models;
 pub mod parser;
 pub mod loader;
 pub mod validator;
+pub mod watcher;
 
 // Re-export commonly used types
 pub use models::{
@@ -13,8 +14,10 @@ pub use models::{
     Route,
     CgiConfig,
     Redirect,
+    TlsConfig,
 };
 
 pub use loader::ConfigLoader;
 pub use parser::IniParser;
-pub use validator::ConfigValidator;
\ No newline at end of file
+pub use validator::ConfigValidator;
+pub use watcher::FileWatcher;
\ No newline at end of file
@@ -13,6 +13,10 @@ pub struct GlobalConfig {
     pub max_body_size: usize,
     pub timeout: u64,
     pub keep_alive: bool,
+    /// Raises `ConfigValidator`'s sanity caps on `max_body_size`,
+    /// `timeout`, and `Route::max_file_size` for operators who
+    /// knowingly want a high-capacity deployment.
+    pub allow_large: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +27,7 @@ pub struct VirtualServer {
     pub is_default: bool,
     pub root: PathBuf,
     pub routes: Vec<Route>,
+    pub tls: Option<TlsConfig>,
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +49,17 @@ pub struct CgiConfig {
     pub executor: PathBuf,
 }
 
+/// HTTPS configuration for a `VirtualServer`. `ports` lists the subset
+/// of the server's `ports` that should be served over TLS using this
+/// certificate/key pair; a server layer can later hand these paths to
+/// rustls.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    pub ports: Vec<u16>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Redirect {
     pub status: u16,
@@ -66,6 +82,7 @@ impl Default for GlobalConfig {
             max_body_size: 1_048_576, // 1MB
             timeout: 30,
             keep_alive: true,
+            allow_large: false,
         }
     }
 }
\ No newline at end of file
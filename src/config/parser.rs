@@ -2,6 +2,18 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+/// Maximum number of substitution passes before a value is considered
+/// circular (e.g. `a -> b -> a`) rather than simply chained.
+const MAX_RESOLVE_ITERATIONS: usize = 16;
+
+/// Placeholder a `%%` escape collapses to mid-resolution, standing in
+/// for a literal '%' until every pass is done. Using the sentinel
+/// instead of '%' itself means an escaped `%{...}` (written `%%{...}`)
+/// can't be rescanned and misread as a real reference on a later pass;
+/// the sentinel is swapped back to '%' once resolution finishes. Chosen
+/// from the Private Use Area so it can't collide with real config text.
+const ESCAPED_PERCENT: char = '\u{E000}';
+
 pub struct IniParser;
 
 impl IniParser {
@@ -47,8 +59,158 @@ impl IniParser {
             }
         }
 
+        Self::resolve_variables(&mut sections)?;
+
         Ok(sections)
     }
+
+    /// Expand `%{section.key}` and `%{env.VAR}` references in every value
+    /// against the already-parsed section map, re-scanning until a pass
+    /// introduces no new substitutions. A fixed iteration cap guards
+    /// against circular references (`a -> b -> a`) looping forever; once
+    /// hit, the offending key is named in the returned error.
+    fn resolve_variables(sections: &mut HashMap<String, HashMap<String, String>>) -> Result<(), String> {
+        for _ in 0..MAX_RESOLVE_ITERATIONS {
+            let snapshot = sections.clone();
+            let mut any_token_left = false;
+
+            for (section_name, entries) in sections.iter_mut() {
+                for (key, value) in entries.iter_mut() {
+                    // Scan for a bare '%' rather than just "%{": a `%%`
+                    // escape on its own (no reference) still needs a
+                    // substitution pass to collapse to the sentinel.
+                    if !value.contains('%') {
+                        continue;
+                    }
+
+                    let key_path = format!("{}.{}", section_name, key);
+                    let resolved = Self::substitute_once(value, &snapshot, &key_path)?;
+
+                    // Only an unresolved `%{...}` token means another
+                    // pass is needed (or, past the cap, a circular
+                    // reference); a collapsed `%%` is already final and
+                    // now wears the sentinel so it can't be mistaken for
+                    // a fresh token on the next pass.
+                    if Self::contains_token(&resolved) {
+                        any_token_left = true;
+                    }
+
+                    *value = resolved;
+                }
+            }
+
+            if !any_token_left {
+                Self::unescape_sentinels(sections);
+                return Ok(());
+            }
+        }
+
+        for (section_name, entries) in sections.iter() {
+            for (key, value) in entries.iter() {
+                if Self::contains_token(value) {
+                    return Err(format!(
+                        "Circular or too-deep variable reference resolving '{}.{}': {}",
+                        section_name, key, value.replace(ESCAPED_PERCENT, "%")
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Swap every collapsed `%%` sentinel back to a literal '%' now that
+    /// resolution is finished and nothing will rescan these values.
+    fn unescape_sentinels(sections: &mut HashMap<String, HashMap<String, String>>) {
+        for entries in sections.values_mut() {
+            for value in entries.values_mut() {
+                if value.contains(ESCAPED_PERCENT) {
+                    *value = value.replace(ESCAPED_PERCENT, "%");
+                }
+            }
+        }
+    }
+
+    fn contains_token(value: &str) -> bool {
+        value.contains("%{")
+    }
+
+    /// Replace every `%{...}` token and `%%` escape in `value` with its
+    /// resolved text. Does not recurse into the replacement text; the
+    /// caller re-scans on the next pass so chained references resolve
+    /// incrementally.
+    fn substitute_once(
+        value: &str,
+        sections: &HashMap<String, HashMap<String, String>>,
+        key_path: &str
+    ) -> Result<String, String> {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+
+        loop {
+            match rest.find('%') {
+                None => {
+                    result.push_str(rest);
+                    break;
+                }
+                Some(idx) => {
+                    result.push_str(&rest[..idx]);
+                    let after = &rest[idx..];
+
+                    if let Some(stripped) = after.strip_prefix("%%") {
+                        result.push(ESCAPED_PERCENT);
+                        rest = stripped;
+                    } else if after.starts_with("%{") {
+                        let close = after.find('}').ok_or_else(||
+                            format!(
+                                "Unterminated variable reference in '{}': missing '}}' in \"{}\"",
+                                key_path, value
+                            )
+                        )?;
+                        let token = &after[2..close];
+                        let resolved = Self::lookup_reference(token, sections, key_path)?;
+                        result.push_str(&resolved);
+                        rest = &after[close + 1..];
+                    } else {
+                        result.push('%');
+                        rest = &after[1..];
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn lookup_reference(
+        token: &str,
+        sections: &HashMap<String, HashMap<String, String>>,
+        key_path: &str
+    ) -> Result<String, String> {
+        let (namespace, key) = token.rsplit_once('.').ok_or_else(||
+            format!(
+                "Malformed variable reference '%{{{}}}' in '{}': expected 'section.key' or 'env.VAR'",
+                token, key_path
+            )
+        )?;
+
+        if namespace == "env" {
+            std::env::var(key).map_err(|_|
+                format!("Unknown environment variable '{}' referenced by '{}'", key, key_path)
+            )
+        } else {
+            sections
+                .get(namespace)
+                .and_then(|s| s.get(key))
+                .cloned()
+                .ok_or_else(||
+                    format!(
+                        "Unknown reference '%{{{}}}' in '{}': no such section/key",
+                        token, key_path
+                    )
+                )
+        }
+    }
 }
 
 #[cfg(test)]
@@ -70,4 +232,71 @@ key3 = value3
         assert_eq!(result.get("section1").unwrap().get("key1").unwrap(), "value1");
         assert_eq!(result.get("section2").unwrap().get("key3").unwrap(), "value3");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_interpolates_reference_between_sections() {
+        let content = r#"
+[server:api]
+root = /srv/api
+
+[route:api:upload]
+upload_dir = %{server:api.root}/uploads
+"#;
+        let result = IniParser::parse_str(content).unwrap();
+        assert_eq!(
+            result.get("route:api:upload").unwrap().get("upload_dir").unwrap(),
+            "/srv/api/uploads"
+        );
+    }
+
+    #[test]
+    fn test_interpolates_env_reference() {
+        std::env::set_var("SERVEX_TEST_ROOT", "/tmp/servex-test");
+        let content = r#"
+[server:api]
+root = %{env.SERVEX_TEST_ROOT}
+"#;
+        let result = IniParser::parse_str(content).unwrap();
+        assert_eq!(result.get("server:api").unwrap().get("root").unwrap(), "/tmp/servex-test");
+        std::env::remove_var("SERVEX_TEST_ROOT");
+    }
+
+    #[test]
+    fn test_literal_percent_escape() {
+        let content = r#"
+[server:api]
+motd = 100%% done
+"#;
+        let result = IniParser::parse_str(content).unwrap();
+        assert_eq!(result.get("server:api").unwrap().get("motd").unwrap(), "100% done");
+    }
+
+    #[test]
+    fn test_escaped_percent_protects_following_token() {
+        let content = r#"
+[server:api]
+motd = %%{foo}
+"#;
+        let result = IniParser::parse_str(content).unwrap();
+        assert_eq!(result.get("server:api").unwrap().get("motd").unwrap(), "%{foo}");
+    }
+
+    #[test]
+    fn test_unknown_reference_is_an_error() {
+        let content = r#"
+[server:api]
+root = %{server:missing.root}
+"#;
+        assert!(IniParser::parse_str(content).is_err());
+    }
+
+    #[test]
+    fn test_circular_reference_is_an_error() {
+        let content = r#"
+[section1]
+a = %{section1.b}
+b = %{section1.a}
+"#;
+        assert!(IniParser::parse_str(content).is_err());
+    }
+}
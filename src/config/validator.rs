@@ -1,19 +1,32 @@
 use super::models::*;
 use std::path::Path;
 
+/// Sane default ceilings, and the much higher ceilings unlocked by
+/// `[global] allow_large = true`, for fields that would otherwise let a
+/// typo (a stray zero, a missing unit) turn into a multi-gigabyte
+/// misconfiguration.
+const DEFAULT_MAX_BODY_SIZE_CAP: usize = 100 * 1024 * 1024; // 100MB
+const LARGE_MAX_BODY_SIZE_CAP: usize = 10 * 1024 * 1024 * 1024; // 10GB
+const DEFAULT_TIMEOUT_CAP: u64 = 300; // 5 minutes
+const LARGE_TIMEOUT_CAP: u64 = 3600; // 1 hour
+const DEFAULT_MAX_FILE_SIZE_CAP: usize = 100 * 1024 * 1024; // 100MB
+const LARGE_MAX_FILE_SIZE_CAP: usize = 10 * 1024 * 1024 * 1024; // 10GB
+
 pub struct ConfigValidator;
 
 impl ConfigValidator {
     /// Validate entire server configuration
     pub fn validate(config: &ServerConfig) -> Result<(), String> {
-        Self::validate_global(&config.global)?;
+        let allow_large = config.global.allow_large;
+        Self::validate_global(&config.global, allow_large)?;
         Self::validate_error_pages(&config.error_pages)?;
-        Self::validate_servers(&config.servers)?;
+        Self::validate_servers(&config.servers, allow_large)?;
         Ok(())
     }
 
-    /// Validate global configuration
-    fn validate_global(global: &GlobalConfig) -> Result<(), String> {
+    /// Validate global configuration, clamp-checking `max_body_size` and
+    /// `timeout` against the default cap unless `allow_large` raises it.
+    fn validate_global(global: &GlobalConfig, allow_large: bool) -> Result<(), String> {
         if global.max_body_size == 0 {
             return Err("max_body_size must be greater than 0".to_string());
         }
@@ -22,6 +35,22 @@ impl ConfigValidator {
             return Err("timeout must be greater than 0".to_string());
         }
 
+        let body_cap = if allow_large { LARGE_MAX_BODY_SIZE_CAP } else { DEFAULT_MAX_BODY_SIZE_CAP };
+        if global.max_body_size > body_cap {
+            return Err(format!(
+                "max_body_size {} exceeds the {} byte cap (set allow_large = true to raise it)",
+                global.max_body_size, body_cap
+            ));
+        }
+
+        let timeout_cap = if allow_large { LARGE_TIMEOUT_CAP } else { DEFAULT_TIMEOUT_CAP };
+        if global.timeout > timeout_cap {
+            return Err(format!(
+                "timeout {} exceeds the {} second cap (set allow_large = true to raise it)",
+                global.timeout, timeout_cap
+            ));
+        }
+
         Ok(())
     }
 
@@ -40,7 +69,7 @@ impl ConfigValidator {
     }
 
     /// Validate all servers
-    fn validate_servers(servers: &[VirtualServer]) -> Result<(), String> {
+    fn validate_servers(servers: &[VirtualServer], allow_large: bool) -> Result<(), String> {
         if servers.is_empty() {
             return Err("At least one server must be defined".to_string());
         }
@@ -48,7 +77,7 @@ impl ConfigValidator {
         // Check for port conflicts
         let mut used_ports = std::collections::HashSet::new();
         for server in servers {
-            Self::validate_server(server)?;
+            Self::validate_server(server, allow_large)?;
 
             for &port in &server.ports {
                 if !used_ports.insert((server.host.clone(), port)) {
@@ -73,7 +102,7 @@ impl ConfigValidator {
     }
 
     /// Validate a single server
-    fn validate_server(server: &VirtualServer) -> Result<(), String> {
+    fn validate_server(server: &VirtualServer, allow_large: bool) -> Result<(), String> {
         // Validate host
         if server.host.is_empty() {
             return Err(format!("Server '{}' has empty host", server.name));
@@ -107,14 +136,158 @@ impl ConfigValidator {
 
         // Validate routes
         for route in &server.routes {
-            Self::validate_route(route)?;
+            Self::validate_route(route, allow_large)?;
+        }
+
+        Self::validate_route_collisions(&server.routes)?;
+
+        Self::validate_tls(server)?;
+
+        Ok(())
+    }
+
+    /// Validate a server's optional TLS configuration: the cert and key
+    /// must exist as regular files, every `tls_ports` entry must also be
+    /// one of the server's plain `ports`, and declaring any TLS port
+    /// without both a cert and key is an error.
+    fn validate_tls(server: &VirtualServer) -> Result<(), String> {
+        let Some(tls) = &server.tls else {
+            return Ok(());
+        };
+
+        let has_cert = !tls.cert.as_os_str().is_empty();
+        let has_key = !tls.key.as_os_str().is_empty();
+
+        if has_cert != has_key {
+            return Err(format!(
+                "Server '{}' has a TLS section with only a {} set; tls_cert and tls_key must be configured together",
+                server.name,
+                if has_cert { "tls_cert" } else { "tls_key" }
+            ));
+        }
+
+        // has_cert == has_key past the guard above, so checking either is
+        // enough to know whether both are present.
+        if !tls.ports.is_empty() && !has_cert {
+            return Err(format!(
+                "Server '{}' declares tls_ports but is missing tls_cert and tls_key",
+                server.name
+            ));
+        }
+
+        if !tls.cert.as_os_str().is_empty() {
+            if !tls.cert.exists() {
+                return Err(format!(
+                    "Server '{}' TLS certificate not found: {:?}",
+                    server.name, tls.cert
+                ));
+            }
+            if !tls.cert.is_file() {
+                return Err(format!(
+                    "Server '{}' TLS certificate is not a file: {:?}",
+                    server.name, tls.cert
+                ));
+            }
+        }
+
+        if !tls.key.as_os_str().is_empty() {
+            if !tls.key.exists() {
+                return Err(format!("Server '{}' TLS key not found: {:?}", server.name, tls.key));
+            }
+            if !tls.key.is_file() {
+                return Err(format!("Server '{}' TLS key is not a file: {:?}", server.name, tls.key));
+            }
+        }
+
+        for &port in &tls.ports {
+            if !server.ports.contains(&port) {
+                return Err(format!(
+                    "Server '{}' tls_ports includes {} which is not in its ports list",
+                    server.name, port
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Split a route path into its segment "shape" for collision
+    /// detection: named parameters and the wildcard compare equal to
+    /// any other parameter/wildcard at the same position regardless of
+    /// their literal spelling, since they would dispatch ambiguously.
+    fn route_shape(path: &str) -> Vec<String> {
+        path.trim_matches('/')
+            .split('/')
+            .map(|segment| {
+                if segment == "*" {
+                    "*".to_string()
+                } else if segment.starts_with('{') && segment.ends_with('}') {
+                    "{}".to_string()
+                } else {
+                    segment.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// Reject routes whose patterns would collide at dispatch time: the
+    /// same segment shape (literal-for-literal, any parameter treated
+    /// as interchangeable with any other) reachable by an overlapping
+    /// HTTP method.
+    fn validate_route_collisions(routes: &[Route]) -> Result<(), String> {
+        for (i, a) in routes.iter().enumerate() {
+            let shape_a = Self::route_shape(&a.path);
+            for b in &routes[i + 1..] {
+                if shape_a != Self::route_shape(&b.path) {
+                    continue;
+                }
+
+                let shares_method = a.methods.iter().any(|m| b.methods.contains(m));
+                if shares_method {
+                    return Err(format!(
+                        "Routes '{}' and '{}' collide on an overlapping HTTP method",
+                        a.path, b.path
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate that `{name}` segments are well-formed and that a
+    /// wildcard, if present, only appears as the final segment.
+    fn validate_route_braces(path: &str) -> Result<(), String> {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        let last = segments.len().saturating_sub(1);
+
+        for (i, segment) in segments.iter().enumerate() {
+            let opens = segment.starts_with('{');
+            let closes = segment.ends_with('}');
+
+            if opens != closes || segment.matches('{').count() > 1 || segment.matches('}').count() > 1 {
+                return Err(format!(
+                    "Malformed parameter segment '{}' in route '{}'",
+                    segment, path
+                ));
+            }
+
+            if opens && closes && segment.len() <= 2 {
+                return Err(format!("Empty parameter name in route '{}'", path));
+            }
+
+            if segment.contains('*') && (segment != &"*" || i != last) {
+                return Err(format!(
+                    "Wildcard '*' must be its own trailing segment in route '{}'",
+                    path
+                ));
+            }
         }
 
         Ok(())
     }
 
     /// Validate a single route
-    fn validate_route(route: &Route) -> Result<(), String> {
+    fn validate_route(route: &Route, allow_large: bool) -> Result<(), String> {
         // Validate path
         if route.path.is_empty() {
             return Err("Route has empty path".to_string());
@@ -124,6 +297,18 @@ impl ConfigValidator {
             return Err(format!("Route path must start with '/': {}", route.path));
         }
 
+        Self::validate_route_braces(&route.path)?;
+
+        if let Some(max_file_size) = route.max_file_size {
+            let cap = if allow_large { LARGE_MAX_FILE_SIZE_CAP } else { DEFAULT_MAX_FILE_SIZE_CAP };
+            if max_file_size > cap {
+                return Err(format!(
+                    "max_file_size {} for route '{}' exceeds the {} byte cap (set allow_large = true to raise it)",
+                    max_file_size, route.path, cap
+                ));
+            }
+        }
+
         // Validate methods
         if route.methods.is_empty() {
             return Err(format!("Route '{}' has no methods defined", route.path));
@@ -211,9 +396,34 @@ mod tests {
             max_body_size: 0,
             timeout: 30,
             keep_alive: true,
+            allow_large: false,
+        };
+
+        assert!(ConfigValidator::validate_global(&global, false).is_err());
+    }
+
+    #[test]
+    fn test_validate_global_rejects_body_size_over_default_cap() {
+        let global = GlobalConfig {
+            max_body_size: DEFAULT_MAX_BODY_SIZE_CAP + 1,
+            timeout: 30,
+            keep_alive: true,
+            allow_large: false,
+        };
+
+        assert!(ConfigValidator::validate_global(&global, false).is_err());
+    }
+
+    #[test]
+    fn test_validate_global_allow_large_raises_cap() {
+        let global = GlobalConfig {
+            max_body_size: DEFAULT_MAX_BODY_SIZE_CAP + 1,
+            timeout: 30,
+            keep_alive: true,
+            allow_large: true,
         };
 
-        assert!(ConfigValidator::validate_global(&global).is_err());
+        assert!(ConfigValidator::validate_global(&global, true).is_ok());
     }
 
     #[test]
@@ -230,7 +440,7 @@ mod tests {
             max_file_size: None,
         };
 
-        assert!(ConfigValidator::validate_route(&route).is_err());
+        assert!(ConfigValidator::validate_route(&route, false).is_err());
     }
 
     #[test]
@@ -247,6 +457,101 @@ mod tests {
             max_file_size: None,
         };
 
-        assert!(ConfigValidator::validate_route(&route).is_err());
+        assert!(ConfigValidator::validate_route(&route, false).is_err());
+    }
+
+    fn make_route(path: &str, methods: &[&str]) -> Route {
+        Route {
+            path: path.to_string(),
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+            root: None,
+            index: None,
+            redirect: None,
+            cgi: None,
+            upload_dir: None,
+            autoindex: false,
+            max_file_size: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_route_malformed_braces() {
+        let route = make_route("/users/{id", &["GET"]);
+        assert!(ConfigValidator::validate_route(&route, false).is_err());
+    }
+
+    #[test]
+    fn test_validate_route_empty_parameter_name() {
+        let route = make_route("/users/{}", &["GET"]);
+        assert!(ConfigValidator::validate_route(&route, false).is_err());
+    }
+
+    #[test]
+    fn test_validate_route_collisions_on_shared_method() {
+        let routes = vec![make_route("/users/{id}", &["GET"]), make_route("/users/{name}", &["GET"])];
+        assert!(ConfigValidator::validate_route_collisions(&routes).is_err());
+    }
+
+    #[test]
+    fn test_validate_route_no_collision_on_disjoint_methods() {
+        let routes = vec![make_route("/users/{id}", &["GET"]), make_route("/users/{id}", &["POST"])];
+        assert!(ConfigValidator::validate_route_collisions(&routes).is_ok());
+    }
+
+    fn make_server(tls: Option<TlsConfig>) -> VirtualServer {
+        VirtualServer {
+            name: "test".to_string(),
+            host: "127.0.0.1".to_string(),
+            ports: vec![443],
+            is_default: true,
+            root: std::path::PathBuf::from("."),
+            routes: vec![],
+            tls,
+        }
+    }
+
+    #[test]
+    fn test_validate_tls_none_is_ok() {
+        assert!(ConfigValidator::validate_tls(&make_server(None)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_tls_ports_without_cert_is_err() {
+        let tls = TlsConfig {
+            cert: std::path::PathBuf::new(),
+            key: std::path::PathBuf::new(),
+            ports: vec![443],
+        };
+        assert!(ConfigValidator::validate_tls(&make_server(Some(tls))).is_err());
+    }
+
+    #[test]
+    fn test_validate_tls_port_not_in_ports_list_is_err() {
+        let tls = TlsConfig {
+            cert: std::path::PathBuf::from("src/main.rs"),
+            key: std::path::PathBuf::from("src/main.rs"),
+            ports: vec![9443],
+        };
+        assert!(ConfigValidator::validate_tls(&make_server(Some(tls))).is_err());
+    }
+
+    #[test]
+    fn test_validate_tls_cert_without_key_is_err() {
+        let tls = TlsConfig {
+            cert: std::path::PathBuf::from("src/main.rs"),
+            key: std::path::PathBuf::new(),
+            ports: vec![],
+        };
+        assert!(ConfigValidator::validate_tls(&make_server(Some(tls))).is_err());
+    }
+
+    #[test]
+    fn test_validate_tls_key_without_cert_is_err() {
+        let tls = TlsConfig {
+            cert: std::path::PathBuf::new(),
+            key: std::path::PathBuf::from("src/main.rs"),
+            ports: vec![],
+        };
+        assert!(ConfigValidator::validate_tls(&make_server(Some(tls))).is_err());
     }
 }
\ No newline at end of file
@@ -0,0 +1,99 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How long a file's last-modified fingerprint must stay unchanged
+/// before a change is considered settled. Coalesces the burst of
+/// rename/truncate/write events many editors emit for a single save.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Polls a single file for changes on a background thread and invokes a
+/// callback once modifications have settled. Re-fingerprints the path
+/// (not a file handle) on every tick, so an editor that replaces the
+/// file via rename or truncate is picked up just like an in-place write.
+///
+/// This polls rather than watching the parent directory with OS-level
+/// notifications, to avoid an undeclared external dependency in a tree
+/// with no manifest to add `notify` to. mtime resolution on some
+/// filesystems is coarse enough (whole seconds on some setups) that two
+/// saves landing in the same tick, with the file ending the same
+/// length, would be indistinguishable from a no-op if the fingerprint
+/// were `(mtime, len)` alone — exactly the missed reload an
+/// event-based watch would have caught. The fingerprint therefore also
+/// hashes the file's contents, so any byte-level change is detected
+/// regardless of mtime/length collisions, at the cost of re-reading the
+/// whole file on every poll tick (acceptable for config-file sizes).
+///
+/// Dropping a `FileWatcher` signals the background thread to stop and
+/// joins it, so it does not spin forever once the handle goes away.
+pub struct FileWatcher {
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl FileWatcher {
+    pub fn watch<F>(path: impl AsRef<Path>, on_change: F) -> Self
+        where F: Fn() + Send + 'static
+    {
+        let path = path.as_ref().to_path_buf();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = Arc::clone(&stop);
+        let handle = thread::spawn(move || Self::run(path, on_change, stop_flag));
+        Self { stop, handle: Some(handle) }
+    }
+
+    fn run(path: PathBuf, on_change: impl Fn(), stop: Arc<AtomicBool>) {
+        let mut last_seen = Self::fingerprint(&path);
+        let mut pending_since: Option<SystemTime> = None;
+
+        while !stop.load(Ordering::Relaxed) {
+            thread::sleep(POLL_INTERVAL);
+            let current = Self::fingerprint(&path);
+
+            if current != last_seen {
+                last_seen = current;
+                pending_since = Some(SystemTime::now());
+                continue;
+            }
+
+            if let Some(since) = pending_since {
+                if since.elapsed().unwrap_or(Duration::ZERO) >= DEBOUNCE {
+                    pending_since = None;
+                    on_change();
+                }
+            }
+        }
+    }
+
+    /// `(mtime, len, content hash)`, or `None` while the file is missing
+    /// or unreadable (e.g. mid-rename). The hash is what actually
+    /// guarantees a real edit is detected; it is recomputed on every
+    /// call, whatever mtime and len show.
+    fn fingerprint(path: &Path) -> Option<(SystemTime, u64, u64)> {
+        let meta = std::fs::metadata(path).ok()?;
+        let modified = meta.modified().ok()?;
+        let hash = Self::content_hash(path)?;
+        Some((modified, meta.len(), hash))
+    }
+
+    fn content_hash(path: &Path) -> Option<u64> {
+        let bytes = std::fs::read(path).ok()?;
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        Some(hasher.finish())
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
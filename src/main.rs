@@ -1,14 +1,106 @@
 mod config;
+mod routing;
+mod upload;
 
 use config::loader::ConfigLoader;
+use config::validator::ConfigValidator;
+
+/// servex: a small configurable HTTP server.
+///
+/// Parsed by hand rather than pulled in from a CLI crate: the rest of
+/// this tree has no external dependencies, and no manifest exists yet
+/// to declare one in.
+#[derive(Debug)]
+struct Cli {
+    /// Path to the server configuration file
+    config: String,
+
+    /// Increase logging verbosity; repeat to stack (-v -v)
+    verbose: u8,
+
+    /// Decrease logging verbosity; repeat to quiet further (-q -q)
+    quiet: u8,
+
+    /// Load and validate the configuration, then exit without starting any listeners
+    check: bool,
+}
+
+impl Cli {
+    /// Parse `args` (expected to include the program name at index 0,
+    /// as `std::env::args()` yields). `-v`/`-q` stack by repetition
+    /// (`-v -v`, not the combined `-vv`); `--config`/`-c` takes the next
+    /// argument as its value.
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut cli = Cli {
+            config: "application.conf".to_string(),
+            verbose: 0,
+            quiet: 0,
+            check: false,
+        };
+
+        let mut args = args.skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-c" | "--config" => {
+                    cli.config = args
+                        .next()
+                        .ok_or_else(|| format!("{} requires a path argument", arg))?;
+                }
+                "-v" | "--verbose" => cli.verbose += 1,
+                "-q" | "--quiet" => cli.quiet += 1,
+                "--check" => cli.check = true,
+                other => return Err(format!("Unrecognized argument: {}", other)),
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum LogLevel {
+    Quiet,
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Cli {
+    /// `-v`/`-q` stack around an `Info` baseline; out-of-range stacking
+    /// saturates at `Quiet`/`Debug` instead of wrapping.
+    fn log_level(&self) -> LogLevel {
+        let levels = [LogLevel::Quiet, LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug];
+        let baseline = 3i32;
+        let index = (baseline + self.verbose as i32 - self.quiet as i32).clamp(0, levels.len() as i32 - 1);
+        levels[index as usize]
+    }
+}
+
+fn log(level: LogLevel, current: LogLevel, message: &str) {
+    if level <= current {
+        println!("{}", message);
+    }
+}
 
 fn main() -> Result<(), String> {
-    // Load configuration
-    let config = ConfigLoader::load("application.conf")?;
-    println!("Loaded {} servers", config.servers.len());
+    let cli = Cli::parse(std::env::args())?;
+    let log_level = cli.log_level();
+
+    log(LogLevel::Info, log_level, &format!("Loading {}", cli.config));
+    let config = ConfigLoader::load(&cli.config)?;
+    ConfigValidator::validate(&config)?;
+
+    if cli.check {
+        println!("{} is valid", cli.config);
+        return Ok(());
+    }
+
+    log(LogLevel::Info, log_level, &format!("Loaded {} servers", config.servers.len()));
     for server in &config.servers {
-        println!(" Server: {} on {:?}", server.host, server.ports);
-        println!("Routes: {}", server.routes.len());
+        log(LogLevel::Debug, log_level, &format!(" Server: {} on {:?}", server.host, server.ports));
+        log(LogLevel::Debug, log_level, &format!("Routes: {}", server.routes.len()));
     }
+
     Ok(())
 }
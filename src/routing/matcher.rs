@@ -0,0 +1,216 @@
+use crate::config::{Route, VirtualServer};
+use std::collections::HashMap;
+
+/// A route resolved for a request path, together with the values
+/// captured from any `{name}` segments it matched.
+pub struct MatchedRoute<'a> {
+    pub route: &'a Route,
+    pub params: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct MatchNode {
+    literal: HashMap<String, MatchNode>,
+    param: Option<(String, Box<MatchNode>)>,
+    // Multiple routes can terminate at the same node when they share a
+    // path but serve disjoint HTTP methods (the validator allows this);
+    // dispatch then picks whichever of these actually serves the
+    // request's method.
+    wildcard_routes: Vec<usize>,
+    routes: Vec<usize>,
+}
+
+/// A segment tree built from a `VirtualServer`'s routes, supporting
+/// literal segments, a single named parameter segment per level
+/// (`{id}`), and a trailing catch-all (`*`). Matching walks the request
+/// path segment by segment preferring literal > named > wildcard, and
+/// backtracks to the next-preferred branch if a literal match turns out
+/// to be a dead end deeper in the tree.
+pub struct RouteMatcher {
+    routes: Vec<Route>,
+    root: MatchNode,
+}
+
+impl RouteMatcher {
+    pub fn new(server: &VirtualServer) -> Self {
+        let routes = server.routes.clone();
+        let mut root = MatchNode::default();
+
+        for (index, route) in routes.iter().enumerate() {
+            let segments = route.path.trim_matches('/').split('/').filter(|s| !s.is_empty());
+            Self::insert(&mut root, segments, index);
+        }
+
+        Self { routes, root }
+    }
+
+    fn insert<'s>(node: &mut MatchNode, mut segments: impl Iterator<Item = &'s str>, index: usize) {
+        match segments.next() {
+            None => node.routes.push(index),
+            Some("*") => node.wildcard_routes.push(index),
+            Some(seg) if seg.starts_with('{') && seg.ends_with('}') => {
+                let name = seg[1..seg.len() - 1].to_string();
+                let (_, child) = node.param.get_or_insert_with(|| (name, Box::new(MatchNode::default())));
+                Self::insert(child, segments, index);
+            }
+            Some(seg) => {
+                let child = node.literal.entry(seg.to_string()).or_default();
+                Self::insert(child, segments, index);
+            }
+        }
+    }
+
+    /// Resolve `path` to the most specific route that also serves
+    /// `method`, or `None` if nothing in the tree serves it.
+    pub fn match_path(&self, path: &str, method: &str) -> Option<MatchedRoute<'_>> {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').filter(|s| !s.is_empty()).collect();
+        let mut params = HashMap::new();
+        let index = Self::walk(&self.root, &segments, method, &self.routes, &mut params)?;
+        let route = &self.routes[index];
+
+        // Routes sharing a parameter level share one node, named after
+        // whichever route was inserted first; key the captures by the
+        // matched route's own `{name}` segments instead.
+        let params = route.path
+            .trim_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .zip(segments.iter())
+            .filter_map(|(pattern, value)| {
+                let name = pattern.strip_prefix('{')?.strip_suffix('}')?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        Some(MatchedRoute { route, params })
+    }
+
+    fn walk(
+        node: &MatchNode,
+        segments: &[&str],
+        method: &str,
+        routes: &[Route],
+        params: &mut HashMap<String, String>
+    ) -> Option<usize> {
+        let Some((seg, rest)) = segments.split_first() else {
+            return Self::pick(&node.routes, method, routes);
+        };
+
+        if let Some(child) = node.literal.get(*seg) {
+            if let Some(index) = Self::walk(child, rest, method, routes, params) {
+                return Some(index);
+            }
+        }
+
+        if let Some((name, child)) = &node.param {
+            let mut attempt = params.clone();
+            attempt.insert(name.clone(), seg.to_string());
+            if let Some(index) = Self::walk(child, rest, method, routes, &mut attempt) {
+                *params = attempt;
+                return Some(index);
+            }
+        }
+
+        Self::pick(&node.wildcard_routes, method, routes)
+    }
+
+    /// Among routes sharing a node, return the first that serves
+    /// `method`.
+    fn pick(candidates: &[usize], method: &str, routes: &[Route]) -> Option<usize> {
+        candidates.iter().copied().find(|&index| routes[index].methods.iter().any(|m| m == method))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn route(path: &str) -> Route {
+        route_with_methods(path, &["GET"])
+    }
+
+    fn route_with_methods(path: &str, methods: &[&str]) -> Route {
+        Route {
+            path: path.to_string(),
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+            root: None,
+            index: None,
+            redirect: None,
+            cgi: None,
+            upload_dir: None,
+            autoindex: false,
+            max_file_size: None,
+        }
+    }
+
+    fn server(routes: Vec<Route>) -> VirtualServer {
+        VirtualServer {
+            name: "test".to_string(),
+            host: "127.0.0.1".to_string(),
+            ports: vec![8080],
+            is_default: true,
+            root: PathBuf::from("."),
+            routes,
+            tls: None,
+        }
+    }
+
+    #[test]
+    fn test_matches_literal_route() {
+        let matcher = RouteMatcher::new(&server(vec![route("/users"), route("/users/{id}")]));
+        let matched = matcher.match_path("/users", "GET").unwrap();
+        assert_eq!(matched.route.path, "/users");
+        assert!(matched.params.is_empty());
+    }
+
+    #[test]
+    fn test_matches_root_route() {
+        let matcher = RouteMatcher::new(&server(vec![route("/")]));
+        let matched = matcher.match_path("/", "GET").unwrap();
+        assert_eq!(matched.route.path, "/");
+    }
+
+    #[test]
+    fn test_captures_named_parameter() {
+        let matcher = RouteMatcher::new(&server(vec![route("/users/{id}")]));
+        let matched = matcher.match_path("/users/42", "GET").unwrap();
+        assert_eq!(matched.route.path, "/users/{id}");
+        assert_eq!(matched.params.get("id").unwrap(), "42");
+    }
+
+    #[test]
+    fn test_literal_preferred_over_named_sibling() {
+        let matcher = RouteMatcher::new(&server(vec![route("/users/{id}"), route("/users/me")]));
+        let matched = matcher.match_path("/users/me", "GET").unwrap();
+        assert_eq!(matched.route.path, "/users/me");
+    }
+
+    #[test]
+    fn test_wildcard_catches_trailing_segments() {
+        let matcher = RouteMatcher::new(&server(vec![route("/static/*")]));
+        let matched = matcher.match_path("/static/css/site.css", "GET").unwrap();
+        assert_eq!(matched.route.path, "/static/*");
+    }
+
+    #[test]
+    fn test_no_match_returns_none() {
+        let matcher = RouteMatcher::new(&server(vec![route("/users")]));
+        assert!(matcher.match_path("/unknown", "GET").is_none());
+    }
+
+    #[test]
+    fn test_same_path_dispatches_by_method() {
+        let matcher = RouteMatcher::new(
+            &server(
+                vec![
+                    route_with_methods("/users/{id}", &["GET"]),
+                    route_with_methods("/users/{id}", &["POST"])
+                ]
+            )
+        );
+
+        assert_eq!(matcher.match_path("/users/42", "GET").unwrap().route.methods, vec!["GET"]);
+        assert_eq!(matcher.match_path("/users/42", "POST").unwrap().route.methods, vec!["POST"]);
+        assert!(matcher.match_path("/users/42", "DELETE").is_none());
+    }
+}
@@ -0,0 +1,6 @@
+// this module turns a `VirtualServer`'s flat route list into a dispatch
+// structure capable of resolving a request path to the route that serves it.
+
+pub mod matcher;
+
+pub use matcher::{MatchedRoute, RouteMatcher};
@@ -0,0 +1,6 @@
+// this module streams incoming upload bodies to disk, honoring the
+// `upload_dir`/`max_file_size` limits a route declares in config.
+
+pub mod writer;
+
+pub use writer::{UploadError, UploadWriter};
@@ -0,0 +1,188 @@
+use crate::config::Route;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Read/write chunk size. Keeps memory use flat regardless of the
+/// uploaded file's size instead of buffering the whole body.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Why an upload was rejected, kept distinct from a generic `String` so
+/// the server layer can map each case to the right HTTP status.
+#[derive(Debug)]
+pub enum UploadError {
+    ForbiddenMethod(String),
+    Forbidden(String),
+    TooLarge { limit: usize },
+    Io(io::Error),
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadError::ForbiddenMethod(method) => write!(f, "method '{}' is not allowed on this route", method),
+            UploadError::Forbidden(reason) => write!(f, "upload forbidden: {}", reason),
+            UploadError::TooLarge { limit } => write!(f, "upload exceeds the {} byte limit", limit),
+            UploadError::Io(err) => write!(f, "upload io error: {}", err),
+        }
+    }
+}
+
+pub struct UploadWriter;
+
+impl UploadWriter {
+    /// Stream `body` into a uniquely named file under `route.upload_dir`,
+    /// aborting and deleting the partial file the moment the byte count
+    /// exceeds `route.max_file_size` (falling back to
+    /// `global_max_body_size` when the route sets none). Returns the
+    /// path written on success.
+    pub fn accept_upload<R: Read>(
+        route: &Route,
+        global_max_body_size: usize,
+        method: &str,
+        client_filename: &str,
+        mut body: R
+    ) -> Result<PathBuf, UploadError> {
+        if !route.methods.iter().any(|m| m == method) {
+            return Err(UploadError::ForbiddenMethod(method.to_string()));
+        }
+
+        let upload_dir = route.upload_dir.as_ref().ok_or_else(||
+            UploadError::Forbidden("route has no upload_dir configured".to_string())
+        )?;
+
+        if !upload_dir.is_dir() {
+            return Err(
+                UploadError::Forbidden(format!("upload_dir does not exist: {:?}", upload_dir))
+            );
+        }
+
+        let filename = Self::sanitize_filename(client_filename)?;
+        let dest = Self::unique_path(upload_dir, &filename);
+        let limit = route.max_file_size.unwrap_or(global_max_body_size);
+
+        let mut file = File::create(&dest).map_err(UploadError::Io)?;
+        let mut buffer = [0u8; BUFFER_SIZE];
+        let mut written: usize = 0;
+
+        loop {
+            let read = body.read(&mut buffer).map_err(UploadError::Io)?;
+            if read == 0 {
+                break;
+            }
+
+            written += read;
+            if written > limit {
+                drop(file);
+                let _ = fs::remove_file(&dest);
+                return Err(UploadError::TooLarge { limit });
+            }
+
+            file.write_all(&buffer[..read]).map_err(UploadError::Io)?;
+        }
+
+        Ok(dest)
+    }
+
+    /// Reject client-supplied filenames that would escape `upload_dir`
+    /// (`..`, `.`, absolute paths) and keep only the bare file name; a
+    /// name with directory parts (`dir/file.txt`) is reduced to its last
+    /// component rather than rejected.
+    fn sanitize_filename(name: &str) -> Result<String, UploadError> {
+        let candidate = Path::new(name);
+
+        if candidate.components().any(|c| !matches!(c, Component::Normal(_))) {
+            return Err(UploadError::Forbidden(format!("unsafe upload filename: {}", name)));
+        }
+
+        candidate
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.to_string())
+            .ok_or_else(|| UploadError::Forbidden(format!("invalid upload filename: {}", name)))
+    }
+
+    fn unique_path(dir: &Path, filename: &str) -> PathBuf {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        dir.join(format!("{}-{}", stamp, filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn route(upload_dir: Option<PathBuf>, max_file_size: Option<usize>) -> Route {
+        Route {
+            path: "/upload".to_string(),
+            methods: vec!["POST".to_string()],
+            root: None,
+            index: None,
+            redirect: None,
+            cgi: None,
+            upload_dir,
+            autoindex: false,
+            max_file_size,
+        }
+    }
+
+    #[test]
+    fn test_rejects_disallowed_method() {
+        let route = route(Some(PathBuf::from(".")), None);
+        let result = UploadWriter::accept_upload(&route, 1024, "GET", "file.txt", Cursor::new(b"hi"));
+        assert!(matches!(result, Err(UploadError::ForbiddenMethod(_))));
+    }
+
+    #[test]
+    fn test_rejects_missing_upload_dir() {
+        let route = route(None, None);
+        let result = UploadWriter::accept_upload(&route, 1024, "POST", "file.txt", Cursor::new(b"hi"));
+        assert!(matches!(result, Err(UploadError::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_rejects_path_traversal_filename() {
+        let route = route(Some(PathBuf::from(".")), None);
+        let result = UploadWriter::accept_upload(
+            &route,
+            1024,
+            "POST",
+            "../../etc/passwd",
+            Cursor::new(b"hi")
+        );
+        assert!(matches!(result, Err(UploadError::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_writes_upload_within_limit() {
+        let dir = std::env::temp_dir().join("servex_upload_writer_test_ok");
+        fs::create_dir_all(&dir).unwrap();
+        let route = route(Some(dir.clone()), Some(1024));
+
+        let dest = UploadWriter::accept_upload(&route, 4096, "POST", "note.txt", Cursor::new(b"hello")).unwrap();
+        assert_eq!(fs::read(&dest).unwrap(), b"hello");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_aborts_and_cleans_up_when_over_limit() {
+        let dir = std::env::temp_dir().join("servex_upload_writer_test_too_large");
+        fs::create_dir_all(&dir).unwrap();
+        let route = route(Some(dir.clone()), Some(4));
+
+        let result = UploadWriter::accept_upload(&route, 4096, "POST", "note.txt", Cursor::new(b"way too big"));
+        assert!(matches!(result, Err(UploadError::TooLarge { .. })));
+
+        let remaining: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+        assert!(remaining.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}